@@ -1,5 +1,5 @@
 use anyhow::{ensure, Context, Result};
-use cargo_metadata::{Metadata, MetadataCommand, Package, PackageId};
+use cargo_metadata::{Metadata, MetadataCommand, Package, PackageId, Target};
 use indexmap::IndexMap;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
@@ -17,60 +17,260 @@ pub struct Cli {
     #[structopt(long)]
     to_json: Option<PathBuf>,
 
-    /// Commit or branch or branch to compare from.
+    /// Range of commits to compare, e.g. `HEAD~5..HEAD` or `main...my-branch`.
+    ///
+    /// An empty left side defaults to the repository's default branch, an
+    /// empty right side defaults to the working tree. The `A...B` form
+    /// compares against the merge base of `A` and `B`, like `git diff`.
+    /// Without `--lockfile`, a side given by `--from-json`/`--to-json` is
+    /// read from that file instead; the revspec is only used for the other
+    /// side.
+    #[structopt(name = "revspec")]
+    revspec: Option<String>,
+
+    /// Restrict the report to only one section of the diff.
+    #[structopt(long, default_value = "added")]
+    only: OnlySection,
+
+    /// Audit the licenses and authors of the newly added dependencies,
+    /// highlighting any license not already in use.
     #[structopt(long)]
-    from: Option<String>,
-
-    /// Commit or branch or branch to compare to.
+    licenses: bool,
+
+    /// Output format for the added-dependencies report.
+    #[structopt(long, default_value = "text")]
+    format: OutputFormat,
+
+    /// Diff the `Cargo.lock` of each endpoint directly instead of running
+    /// `cargo metadata`.
+    ///
+    /// This is much faster and works on commits that no longer build, at
+    /// the cost of not being able to tell which features pulled in a
+    /// dependency (`--licenses` and `--format=json` are not available in
+    /// this mode).
     #[structopt(long)]
-    to: Option<String>,
+    lockfile: bool,
+}
+
+/// Output format for the report produced by [`Report`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            other => anyhow::bail!(
+                "invalid value for --format: `{}` (expected `text` or `json`)",
+                other
+            ),
+        }
+    }
+}
+
+/// Which section of the dependency diff to print.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OnlySection {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl std::str::FromStr for OnlySection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "added" => Ok(OnlySection::Added),
+            "removed" => Ok(OnlySection::Removed),
+            "changed" => Ok(OnlySection::Changed),
+            other => anyhow::bail!(
+                "invalid value for --only: `{}` (expected `added`, `removed` or `changed`)",
+                other
+            ),
+        }
+    }
+}
+
+/// One side of a comparison: either a git commit/branch or the working tree.
+enum Endpoint {
+    Commit(String),
+    WorkingTree,
+}
+
+impl Endpoint {
+    fn read_metadata(&self) -> Result<Metadata> {
+        match self {
+            Endpoint::Commit(commit) => Cli::read_metadata_from_commit(commit),
+            Endpoint::WorkingTree => MetadataCommand::new()
+                .exec()
+                .context("could not parse metadata"),
+        }
+    }
+
+    fn read_lockfile(&self) -> Result<cargo_lock::Lockfile> {
+        match self {
+            Endpoint::Commit(commit) => Cli::read_lockfile_from_commit(commit),
+            Endpoint::WorkingTree => {
+                cargo_lock::Lockfile::load("Cargo.lock").context("could not read Cargo.lock")
+            }
+        }
+    }
 }
 
 impl Cli {
     pub fn run(self) -> Result<()> {
-        let from_metadata = if let Some(path) = self.from_json {
-            Self::read_metadata_from_json(path)?
-        } else if let Some(commit) = self.from.as_deref() {
-            Self::read_metadata_from_commit(commit)?
+        if self.lockfile {
+            ensure!(
+                !self.licenses && self.format == OutputFormat::Text,
+                "--licenses and --format=json are not available with --lockfile"
+            );
+
+            let (from_endpoint, to_endpoint) = Self::resolve_endpoints(self.revspec.as_deref())?;
+            let from_lockfile = from_endpoint.read_lockfile()?;
+            let to_lockfile = to_endpoint.read_lockfile()?;
+            let diff = LockfileDiff::new(&from_lockfile, &to_lockfile);
+
+            match self.only {
+                OnlySection::Added => diff.print_added(),
+                OnlySection::Removed => diff.print_removed(),
+                OnlySection::Changed => diff.print_changed(),
+            }
+
+            return Ok(());
+        }
+
+        // Only resolve git endpoints when at least one side still needs one,
+        // so a fully `--from-json`/`--to-json` invocation never touches git.
+        let endpoints = if self.from_json.is_some() && self.to_json.is_some() {
+            None
         } else {
-            let commit = Self::git_default_branch()?;
-            Self::read_metadata_from_commit(commit)?
+            Some(Self::resolve_endpoints(self.revspec.as_deref())?)
         };
 
-        let to_metadata = if let Some(path) = self.to_json {
-            Self::read_metadata_from_json(path)?
-        } else if let Some(commit) = self.to.as_deref() {
-            Self::read_metadata_from_commit(commit)?
-        } else {
-            MetadataCommand::new()
-                .exec()
-                .context("could not parse metadata")?
+        let from_metadata = match self.from_json {
+            Some(path) => Self::read_metadata_from_json(path)?,
+            None => endpoints.as_ref().unwrap().0.read_metadata()?,
+        };
+
+        let to_metadata = match self.to_json {
+            Some(path) => Self::read_metadata_from_json(path)?,
+            None => endpoints.as_ref().unwrap().1.read_metadata()?,
         };
 
         let diff = MetadataDiff::new(&from_metadata, &to_metadata);
-        let new_packages = diff.collect_new_dependencies();
 
-        use ansi_term::Color::*;
+        if self.licenses {
+            Self::print_licenses(&diff);
+            return Ok(());
+        }
 
-        for ((dep_id, features), parents) in new_packages {
-            print!("{}", Green.bold().paint(&diff.new_map[dep_id].name));
-            if !features.is_empty() {
-                for feature in features.iter() {
-                    print!(" +{}", Red.bold().paint(&**feature));
+        match self.only {
+            OnlySection::Added => {
+                let report = Report::from_new_dependencies(&diff);
+                match self.format {
+                    OutputFormat::Text => report.render_text(),
+                    OutputFormat::Json => report.render_json()?,
                 }
             }
+            OnlySection::Removed => Self::print_removed(&diff),
+            OnlySection::Changed => Self::print_changed(&diff),
+        }
+
+        Ok(())
+    }
+
+    fn print_licenses(diff: &MetadataDiff) {
+        use ansi_term::Color::*;
+
+        let old_licenses = diff
+            .old_map
+            .values()
+            .filter_map(|package| package.license.as_deref())
+            .collect::<HashSet<_>>();
+        let saw_unlicensed = diff
+            .old_map
+            .values()
+            .any(|package| package.license.is_none());
+
+        for ((dep_id, _features), _parents) in diff.collect_new_dependencies() {
+            let package = diff.new_map[dep_id];
+
+            print!("{} license: ", Green.bold().paint(&package.name));
+            match &package.license {
+                Some(license) if old_licenses.contains(license.as_str()) => print!("{}", license),
+                Some(license) => print!("{}", Red.bold().paint(license.as_str())),
+                None if saw_unlicensed => print!("<unknown>"),
+                None => print!("{}", Red.bold().paint("<unknown>")),
+            }
+            if let Some(license_file) = &package.license_file {
+                print!(" (file: {})", license_file);
+            }
+            if !package.authors.is_empty() {
+                print!(" authors: {}", package.authors.join(", "));
+            }
+            println!();
+        }
+    }
+
+    fn print_removed(diff: &MetadataDiff) {
+        use ansi_term::Color::*;
+
+        for ((dep_id, _features), parents) in diff.collect_removed_dependencies() {
+            let package = diff.old_map[dep_id];
+            print!(
+                "{} {}",
+                Red.bold().paint("-"),
+                Red.bold().paint(&package.name)
+            );
             let mut it = parents.iter();
             print!(
                 " pulled by: {}",
-                Yellow.bold().paint(&diff.new_map[*it.next().unwrap()].name)
+                Yellow.bold().paint(&diff.old_map[*it.next().unwrap()].name)
             );
             for parent_id in it {
-                print!(", {}", Yellow.bold().paint(&diff.new_map[*parent_id].name));
+                print!(", {}", Yellow.bold().paint(&diff.old_map[*parent_id].name));
             }
             println!();
         }
+    }
 
-        Ok(())
+    fn print_changed(diff: &MetadataDiff) {
+        use ansi_term::Color::*;
+
+        for (old_package, new_package) in diff.collect_changed_versions() {
+            println!(
+                "{} {} -> {}",
+                Yellow.bold().paint(&old_package.name),
+                old_package.version,
+                new_package.version
+            );
+        }
+    }
+
+    /// Return the `⚠` annotations that apply to a newly added package, i.e.
+    /// the ones that call out code executed at build time.
+    fn build_time_warnings(package: &Package) -> Vec<&'static str> {
+        let mut warnings = Vec::new();
+
+        if package.targets.iter().any(Target::is_custom_build) {
+            warnings.push("⚠ build-script");
+        }
+        if package
+            .targets
+            .iter()
+            .any(|target| target.kind.iter().any(|kind| kind == "proc-macro"))
+        {
+            warnings.push("⚠ proc-macro");
+        }
+
+        warnings
     }
 
     fn read_metadata_from_json(path: impl AsRef<Path>) -> Result<Metadata> {
@@ -110,6 +310,36 @@ impl Cli {
         Ok(metadata)
     }
 
+    fn read_lockfile_from_commit(commit: impl AsRef<str>) -> Result<cargo_lock::Lockfile> {
+        let commit = commit.as_ref();
+        let output = Command::new("git")
+            .arg("show")
+            .arg(format!("{}:Cargo.lock", commit))
+            .output()
+            .context("could not start command git")?;
+        ensure!(
+            output.status.success(),
+            "could not read Cargo.lock at {}",
+            commit
+        );
+
+        String::from_utf8_lossy(&output.stdout)
+            .parse()
+            .with_context(|| format!("could not parse Cargo.lock at {}", commit))
+    }
+
+    /// Resolve the two endpoints to compare from an optional `revspec`,
+    /// defaulting to the repository's default branch and the working tree.
+    fn resolve_endpoints(revspec: Option<&str>) -> Result<(Endpoint, Endpoint)> {
+        match revspec {
+            Some(revspec) => Self::parse_revspec(revspec),
+            None => Ok((
+                Endpoint::Commit(Self::git_default_branch()?),
+                Endpoint::WorkingTree,
+            )),
+        }
+    }
+
     fn git_default_branch() -> Result<String> {
         let output = Command::new("git")
             .args(&["symbolic-ref", "refs/remotes/origin/HEAD"])
@@ -120,6 +350,58 @@ impl Cli {
 
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     }
+
+    /// Parse a `A..B` or `A...B` revspec into its two endpoints.
+    fn parse_revspec(revspec: &str) -> Result<(Endpoint, Endpoint)> {
+        if let Some((left, right)) = revspec.split_once("...") {
+            let from_ref = if left.is_empty() {
+                Self::git_default_branch()?
+            } else {
+                left.to_string()
+            };
+            let to_ref = if right.is_empty() { "HEAD" } else { right };
+            let merge_base = Self::git_merge_base(&from_ref, to_ref)?;
+
+            let to = if right.is_empty() {
+                Endpoint::WorkingTree
+            } else {
+                Endpoint::Commit(right.to_string())
+            };
+
+            Ok((Endpoint::Commit(merge_base), to))
+        } else if let Some((left, right)) = revspec.split_once("..") {
+            let from = if left.is_empty() {
+                Endpoint::Commit(Self::git_default_branch()?)
+            } else {
+                Endpoint::Commit(left.to_string())
+            };
+            let to = if right.is_empty() {
+                Endpoint::WorkingTree
+            } else {
+                Endpoint::Commit(right.to_string())
+            };
+
+            Ok((from, to))
+        } else {
+            Ok((Endpoint::Commit(revspec.to_string()), Endpoint::WorkingTree))
+        }
+    }
+
+    fn git_merge_base(from: impl AsRef<str>, to: impl AsRef<str>) -> Result<String> {
+        let output = Command::new("git")
+            .args(&["merge-base", from.as_ref(), to.as_ref()])
+            .stderr(Stdio::inherit())
+            .output()
+            .context("could not start command git")?;
+        ensure!(
+            output.status.success(),
+            "could not find merge base of {} and {}",
+            from.as_ref(),
+            to.as_ref()
+        );
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
 }
 
 fn main() -> Result<()> {
@@ -129,6 +411,79 @@ fn main() -> Result<()> {
     Cli::from_iter(command.into_iter().chain(args)).run()
 }
 
+/// A rendering-agnostic view of the added-dependencies diff, so that the
+/// text and JSON outputs are always built from the same data.
+struct Report {
+    entries: Vec<ReportEntry>,
+}
+
+#[derive(serde::Serialize)]
+struct ReportEntry {
+    name: String,
+    version: String,
+    source: Option<String>,
+    features: Vec<String>,
+    pulled_by: Vec<String>,
+    /// e.g. `⚠ build-script`, `⚠ proc-macro` — see [`Cli::build_time_warnings`].
+    warnings: Vec<String>,
+}
+
+impl Report {
+    fn from_new_dependencies(diff: &MetadataDiff) -> Self {
+        let entries = diff
+            .collect_new_dependencies()
+            .into_iter()
+            .map(|((dep_id, features), parents)| {
+                let package = diff.new_map[dep_id];
+                ReportEntry {
+                    name: package.name.clone(),
+                    version: package.version.to_string(),
+                    source: package.source.as_ref().map(|source| source.repr.clone()),
+                    features: features.into_iter().map(str::to_string).collect(),
+                    pulled_by: parents
+                        .into_iter()
+                        .map(|parent_id| diff.new_map[parent_id].name.clone())
+                        .collect(),
+                    warnings: Cli::build_time_warnings(package)
+                        .into_iter()
+                        .map(str::to_string)
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Report { entries }
+    }
+
+    fn render_text(&self) {
+        use ansi_term::Color::*;
+
+        for entry in &self.entries {
+            print!("{}", Green.bold().paint(&entry.name));
+            for feature in &entry.features {
+                print!(" +{}", Red.bold().paint(feature.as_str()));
+            }
+            for warning in &entry.warnings {
+                print!(" {}", Purple.bold().paint(warning.as_str()));
+            }
+            print!(
+                " pulled by: {}",
+                Yellow.bold().paint(entry.pulled_by.join(", "))
+            );
+            println!();
+        }
+    }
+
+    fn render_json(&self) -> Result<()> {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&self.entries)
+                .context("could not serialize report to JSON")?
+        );
+        Ok(())
+    }
+}
+
 struct MetadataDiff<'a> {
     old_metadata: &'a Metadata,
     new_metadata: &'a Metadata,
@@ -178,6 +533,94 @@ impl<'a> MetadataDiff<'a> {
         new_packages
     }
 
+    pub fn collect_removed_dependencies(
+        &'a self,
+    ) -> IndexMap<(&'a PackageId, Vec<&'a str>), Vec<&'a PackageId>> {
+        let old_deps = Self::collect_dependencies(&self.old_metadata, &self.old_map);
+        let new_deps = Self::collect_dependencies(&self.new_metadata, &self.new_map);
+        let diff = old_deps
+            .into_iter()
+            .filter(|(_, _, old_dep_id)| {
+                !new_deps.iter().any(|(_, _, new_dep_id)| {
+                    self.old_map[old_dep_id].name == self.new_map[new_dep_id].name
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut removed_packages =
+            diff.into_iter()
+                .fold(IndexMap::new(), |mut acc, (parent_id, features, dep_id)| {
+                    let mut features = features.into_iter().collect::<Vec<_>>();
+                    features.sort_unstable();
+                    acc.entry((dep_id, features))
+                        .or_insert(Vec::new())
+                        .push(parent_id);
+                    acc
+                });
+        removed_packages.sort_keys();
+
+        removed_packages
+    }
+
+    /// Report a crate as changed only when exactly one of its versions
+    /// disappeared and exactly one new one took its place: an unambiguous
+    /// version bump. Crates where several versions disappeared or several
+    /// appeared are left alone, since there's no single pairing to report;
+    /// a crate with several coexisting versions (e.g. `syn` 1.x and 2.x) is
+    /// still reported when exactly one of them was swapped — mirrors
+    /// [`LockfileDiff::collect_changed`].
+    pub fn collect_changed_versions(&'a self) -> Vec<(&'a Package, &'a Package)> {
+        let old_deps = Self::collect_dependencies(&self.old_metadata, &self.old_map);
+        let new_deps = Self::collect_dependencies(&self.new_metadata, &self.new_map);
+
+        let old_packages_by_name = Self::packages_by_name(&old_deps, &self.old_map);
+        let new_packages_by_name = Self::packages_by_name(&new_deps, &self.new_map);
+
+        let mut names = old_packages_by_name
+            .keys()
+            .filter(|name| new_packages_by_name.contains_key(*name))
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let old_packages = &old_packages_by_name[name];
+                let new_packages = &new_packages_by_name[name];
+
+                let removed = old_packages
+                    .iter()
+                    .filter(|old| !new_packages.iter().any(|new| new.version == old.version))
+                    .collect::<Vec<_>>();
+                let added = new_packages
+                    .iter()
+                    .filter(|new| !old_packages.iter().any(|old| old.version == new.version))
+                    .collect::<Vec<_>>();
+
+                match (removed.as_slice(), added.as_slice()) {
+                    ([old_package], [new_package]) => Some((**old_package, **new_package)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Unique packages (deduped by id) referenced as a dependency target,
+    /// grouped by crate name.
+    fn packages_by_name(
+        deps: &[(&'a PackageId, HashSet<&'a str>, &'a PackageId)],
+        map: &HashMap<&'a PackageId, &'a Package>,
+    ) -> HashMap<&'a str, Vec<&'a Package>> {
+        let mut packages: HashMap<&'a str, Vec<&'a Package>> = HashMap::new();
+        for (_, _, dep_id) in deps {
+            let package = map[dep_id];
+            let bucket = packages.entry(package.name.as_str()).or_default();
+            if !bucket.iter().any(|p| p.id == package.id) {
+                bucket.push(package);
+            }
+        }
+        packages
+    }
+
     fn collect_packages_map(metadata: &'a Metadata) -> HashMap<&'a PackageId, &'a Package> {
         metadata.packages.iter().map(|x| (&x.id, x)).collect()
     }
@@ -186,16 +629,27 @@ impl<'a> MetadataDiff<'a> {
         metadata: &'a Metadata,
         map: &'a HashMap<&'a PackageId, &'a Package>,
     ) -> Vec<(&'a PackageId, HashSet<&'a str>, &'a PackageId)> {
+        let workspace_member_ids = metadata.workspace_members.iter().collect::<HashSet<_>>();
+
+        // A first-level dependency is itself allowed to be a parent below,
+        // alongside the workspace members, so that both depth-1 edges (workspace member ->
+        // direct dependency) and depth-2 edges (direct dependency -> its own
+        // dependency) are kept.
         let first_level_dependencies = metadata
             .resolve
             .as_ref()
             .unwrap()
             .nodes
             .iter()
-            .filter(|node| metadata.workspace_members.contains(&node.id))
+            .filter(|node| workspace_member_ids.contains(&node.id))
             .flat_map(|node| &node.dependencies)
             .collect::<HashSet<_>>();
 
+        let allowed_parents = workspace_member_ids
+            .union(&first_level_dependencies)
+            .copied()
+            .collect::<HashSet<_>>();
+
         metadata
             .resolve
             .as_ref()
@@ -240,7 +694,7 @@ impl<'a> MetadataDiff<'a> {
 
                 (&parent_package.id, dep_features, &dep_package.id)
             })
-            .filter(|(parent_id, _, _)| first_level_dependencies.contains(parent_id))
+            .filter(|(parent_id, _, _)| allowed_parents.contains(parent_id))
             .collect()
     }
 }
@@ -250,6 +704,164 @@ fn strip_fragment(mut url: url::Url) -> url::Url {
     url
 }
 
+/// A lighter counterpart to [`MetadataDiff`] that compares two `Cargo.lock`
+/// files directly, without resolving features. Used by `--lockfile`.
+///
+/// Packages are keyed by `(name, version)` rather than just `name`, since a
+/// single lockfile routinely resolves more than one version of the same
+/// crate (e.g. `syn 1.x` and `syn 2.x` coexisting).
+struct LockfileDiff<'a> {
+    old_map: HashMap<(&'a str, &'a cargo_lock::Version), &'a cargo_lock::Package>,
+    new_map: HashMap<(&'a str, &'a cargo_lock::Version), &'a cargo_lock::Package>,
+}
+
+impl<'a> LockfileDiff<'a> {
+    fn new(old_lockfile: &'a cargo_lock::Lockfile, new_lockfile: &'a cargo_lock::Lockfile) -> Self {
+        Self {
+            old_map: Self::collect_packages_map(old_lockfile),
+            new_map: Self::collect_packages_map(new_lockfile),
+        }
+    }
+
+    fn collect_packages_map(
+        lockfile: &'a cargo_lock::Lockfile,
+    ) -> HashMap<(&'a str, &'a cargo_lock::Version), &'a cargo_lock::Package> {
+        lockfile
+            .packages
+            .iter()
+            .map(|package| ((package.name.as_str(), &package.version), package))
+            .collect()
+    }
+
+    /// Group each map's keys by crate name, for the `changed` report.
+    fn versions_by_name(
+        map: &HashMap<(&'a str, &'a cargo_lock::Version), &'a cargo_lock::Package>,
+    ) -> HashMap<&'a str, Vec<&'a cargo_lock::Version>> {
+        let mut versions = HashMap::new();
+        for (name, version) in map.keys() {
+            versions
+                .entry(*name)
+                .or_insert_with(Vec::new)
+                .push(*version);
+        }
+        versions
+    }
+
+    /// Packages newly present in `new_map`, excluding those already reported
+    /// as a version bump by [`Self::collect_changed`] (a bump is neither a
+    /// brand-new dependency nor a removed one).
+    fn collect_added(&'a self) -> Vec<&'a cargo_lock::Package> {
+        let changed_new_keys = self
+            .collect_changed()
+            .into_iter()
+            .map(|(name, _, new_version)| (name, new_version))
+            .collect::<HashSet<_>>();
+
+        let mut keys = self
+            .new_map
+            .keys()
+            .filter(|key| !self.old_map.contains_key(*key) && !changed_new_keys.contains(*key))
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+
+        keys.into_iter().map(|key| self.new_map[key]).collect()
+    }
+
+    /// Packages no longer present in `new_map`, excluding those already
+    /// reported as a version bump by [`Self::collect_changed`].
+    fn collect_removed(&'a self) -> Vec<&'a cargo_lock::Package> {
+        let changed_old_keys = self
+            .collect_changed()
+            .into_iter()
+            .map(|(name, old_version, _)| (name, old_version))
+            .collect::<HashSet<_>>();
+
+        let mut keys = self
+            .old_map
+            .keys()
+            .filter(|key| !self.new_map.contains_key(*key) && !changed_old_keys.contains(*key))
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+
+        keys.into_iter().map(|key| self.old_map[key]).collect()
+    }
+
+    /// Report a crate as changed only when exactly one of its versions
+    /// disappeared and exactly one new one took its place: an unambiguous
+    /// version bump. Crates where several versions disappeared or several
+    /// appeared are left alone, since there's no single pairing to report;
+    /// a crate with several coexisting versions is still reported when
+    /// exactly one of them was swapped.
+    fn collect_changed(
+        &'a self,
+    ) -> Vec<(&'a str, &'a cargo_lock::Version, &'a cargo_lock::Version)> {
+        let old_versions = Self::versions_by_name(&self.old_map);
+        let new_versions = Self::versions_by_name(&self.new_map);
+
+        let mut names = old_versions
+            .keys()
+            .filter(|name| new_versions.contains_key(*name))
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let removed = old_versions[name]
+                    .iter()
+                    .filter(|version| !new_versions[name].contains(version))
+                    .collect::<Vec<_>>();
+                let added = new_versions[name]
+                    .iter()
+                    .filter(|version| !old_versions[name].contains(version))
+                    .collect::<Vec<_>>();
+
+                match (removed.as_slice(), added.as_slice()) {
+                    ([old_version], [new_version]) => Some((*name, **old_version, **new_version)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    fn print_added(&'a self) {
+        use ansi_term::Color::*;
+
+        for package in self.collect_added() {
+            println!(
+                "{} {}",
+                Green.bold().paint(package.name.as_str()),
+                package.version
+            );
+        }
+    }
+
+    fn print_removed(&'a self) {
+        use ansi_term::Color::*;
+
+        for package in self.collect_removed() {
+            println!(
+                "{} {}",
+                Red.bold().paint(package.name.as_str()),
+                package.version
+            );
+        }
+    }
+
+    fn print_changed(&'a self) {
+        use ansi_term::Color::*;
+
+        for (name, old_version, new_version) in self.collect_changed() {
+            println!(
+                "{} {} -> {}",
+                Yellow.bold().paint(name),
+                old_version,
+                new_version
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -273,4 +885,70 @@ mod tests {
             assert!(got == expected);
         }
     }
+
+    #[test]
+    fn scenario_2_removed_and_changed() {
+        const OUTPUT_PATH: &str = "tests/scenario_2.stdout";
+
+        let before =
+            MetadataCommand::parse(include_str!("../tests/fixtures/scenario_2_before.json"))
+                .unwrap();
+        let after = MetadataCommand::parse(include_str!("../tests/fixtures/scenario_2_after.json"))
+            .unwrap();
+        let diff = MetadataDiff::new(&before, &after);
+        // Summarize to (name, version) pairs rather than dumping the full
+        // `Package`: it carries a `features: HashMap<..>` field whose Debug
+        // order isn't stable across runs.
+        let changed = diff
+            .collect_changed_versions()
+            .into_iter()
+            .map(|(old, new)| (&old.name, &old.version, &new.version))
+            .collect::<Vec<_>>();
+        let got = format!(
+            "removed:\n{:#?}\nchanged:\n{:#?}",
+            diff.collect_removed_dependencies(),
+            changed
+        );
+
+        if std::env::var("OVERWRITE").is_ok() || !Path::new(OUTPUT_PATH).exists() {
+            fs::write(&OUTPUT_PATH, got).unwrap();
+        } else {
+            let expected = fs::read_to_string(&OUTPUT_PATH).unwrap();
+            println!("{}", prettydiff::diff_lines(&expected, &got));
+            assert!(got == expected);
+        }
+    }
+
+    #[test]
+    fn scenario_lockfile() {
+        const OUTPUT_PATH: &str = "tests/scenario_lockfile.stdout";
+
+        let old: cargo_lock::Lockfile = include_str!("../tests/fixtures/lockfile_old.lock")
+            .parse()
+            .unwrap();
+        let new: cargo_lock::Lockfile = include_str!("../tests/fixtures/lockfile_new.lock")
+            .parse()
+            .unwrap();
+        let diff = LockfileDiff::new(&old, &new);
+        let got = format!(
+            "added:\n{:#?}\nremoved:\n{:#?}\nchanged:\n{:#?}",
+            diff.collect_added()
+                .into_iter()
+                .map(|package| (package.name.as_str(), &package.version))
+                .collect::<Vec<_>>(),
+            diff.collect_removed()
+                .into_iter()
+                .map(|package| (package.name.as_str(), &package.version))
+                .collect::<Vec<_>>(),
+            diff.collect_changed(),
+        );
+
+        if std::env::var("OVERWRITE").is_ok() || !Path::new(OUTPUT_PATH).exists() {
+            fs::write(&OUTPUT_PATH, got).unwrap();
+        } else {
+            let expected = fs::read_to_string(&OUTPUT_PATH).unwrap();
+            println!("{}", prettydiff::diff_lines(&expected, &got));
+            assert!(got == expected);
+        }
+    }
 }